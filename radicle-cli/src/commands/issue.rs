@@ -1,4 +1,5 @@
 #![allow(clippy::or_fun_call)]
+use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::str::FromStr;
 
@@ -28,14 +29,23 @@ Usage
 
     rad issue
     rad issue new [--title <title>] [--description <text>]
-    rad issue show <id>
+    rad issue show <id> [--before <comment-id>] [--after <comment-id>] [--limit <n>]
+    rad issue comment <id> [--reply-to <comment-id>] [--message <text>]
     rad issue state <id> [--closed | --open | --solved]
     rad issue delete <id>
     rad issue react <id> [--emoji <char>]
-    rad issue list [--assigned <key>]
+    rad issue assign <id> --add <key> [--add <key>..]
+    rad issue unassign <id> --delete <key> [--delete <key>..]
+    rad issue list [<option>..]
 
 Options
 
+    --assigned <key>         Only show issues assigned to this key
+    --state <state>          Filter by state (open, closed, solved)
+    --author <key>           Filter by author
+    --tag <label>            Filter by tag (can be given multiple times)
+    --search <substr>        Filter by a substring of the title or description
+
     --help      Print help
     --payload   Print JSON output like HTTP API
 "#,
@@ -49,6 +59,8 @@ pub struct Metadata {
 
 #[derive(Default, Debug, PartialEq, Eq)]
 pub enum OperationName {
+    Assign,
+    Comment,
     Create,
     Delete,
     #[default]
@@ -56,6 +68,7 @@ pub enum OperationName {
     React,
     Show,
     State,
+    Unassign,
 }
 
 /// Command line Peer argument.
@@ -66,6 +79,55 @@ pub enum Assigned {
     Peer(cob::ActorId),
 }
 
+/// State filter for `list`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StateFilter {
+    Open,
+    Closed,
+    Solved,
+}
+
+impl StateFilter {
+    /// Whether the given issue state matches this filter.
+    fn matches(&self, state: &State) -> bool {
+        match (self, state) {
+            (StateFilter::Open, State::Open) => true,
+            (StateFilter::Closed, State::Closed { .. }) => true,
+            (
+                StateFilter::Solved,
+                State::Closed {
+                    reason: CloseReason::Solved,
+                },
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for StateFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(StateFilter::Open),
+            "closed" => Ok(StateFilter::Closed),
+            "solved" => Ok(StateFilter::Solved),
+            _ => Err(anyhow!("invalid state '{}'", s)),
+        }
+    }
+}
+
+/// A window into an issue's discussion, in timestamp order.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Page {
+    /// Only return comments before this one.
+    pub before: Option<CommentId>,
+    /// Only return comments after this one.
+    pub after: Option<CommentId>,
+    /// The maximum number of comments to return.
+    pub limit: Option<usize>,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Operation {
     Create {
@@ -75,6 +137,12 @@ pub enum Operation {
     Show {
         id: IssueId,
         json: Option<bool>,
+        page: Page,
+    },
+    Comment {
+        id: IssueId,
+        reply_to: Option<CommentId>,
+        message: Option<String>,
     },
     State {
         id: IssueId,
@@ -87,8 +155,20 @@ pub enum Operation {
         id: IssueId,
         reaction: Reaction,
     },
+    Assign {
+        id: IssueId,
+        assignees: Vec<PublicKey>,
+    },
+    Unassign {
+        id: IssueId,
+        assignees: Vec<PublicKey>,
+    },
     List {
         assigned: Option<Assigned>,
+        state: Option<StateFilter>,
+        author: Option<PublicKey>,
+        tags: Vec<Tag>,
+        search: Option<String>,
     },
 }
 
@@ -109,6 +189,16 @@ impl Args for Options {
         let mut reaction: Option<Reaction> = None;
         let mut description: Option<String> = None;
         let mut state: Option<State> = None;
+        let mut assignees: Vec<PublicKey> = Vec::new();
+        let mut list_state: Option<StateFilter> = None;
+        let mut author: Option<PublicKey> = None;
+        let mut tags: Vec<Tag> = Vec::new();
+        let mut search: Option<String> = None;
+        let mut reply_to: Option<CommentId> = None;
+        let mut message: Option<String> = None;
+        let mut before: Option<CommentId> = None;
+        let mut after: Option<CommentId> = None;
+        let mut limit: Option<usize> = None;
         let mut json_out: Option<bool> = Some(false);
 
         while let Some(arg) = parser.next()? {
@@ -144,6 +234,74 @@ impl Args for Options {
                 Long("description") if op == Some(OperationName::Create) => {
                     description = Some(parser.value()?.to_string_lossy().into());
                 }
+                Long("add") if op == Some(OperationName::Assign) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    let key = PublicKey::from_str(&val)
+                        .map_err(|_| anyhow!("invalid key '{}'", val))?;
+                    assignees.push(key);
+                }
+                Long("delete") if op == Some(OperationName::Unassign) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    let key = PublicKey::from_str(&val)
+                        .map_err(|_| anyhow!("invalid key '{}'", val))?;
+                    assignees.push(key);
+                }
+                Long("state") if op == Some(OperationName::List) => {
+                    let val = parser.value()?;
+                    list_state = Some(StateFilter::from_str(&val.to_string_lossy())?);
+                }
+                Long("author") if op == Some(OperationName::List) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    author = Some(
+                        PublicKey::from_str(&val).map_err(|_| anyhow!("invalid key '{}'", val))?,
+                    );
+                }
+                Long("tag") if op == Some(OperationName::List) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    tags.push(Tag::from_str(&val).map_err(|_| anyhow!("invalid tag '{}'", val))?);
+                }
+                Long("search") if op == Some(OperationName::List) => {
+                    search = Some(parser.value()?.to_string_lossy().into());
+                }
+                Long("message") if op == Some(OperationName::Comment) => {
+                    message = Some(parser.value()?.to_string_lossy().into());
+                }
+                Long("reply-to") if op == Some(OperationName::Comment) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    reply_to = Some(
+                        CommentId::from_str(&val)
+                            .map_err(|_| anyhow!("invalid comment id '{}'", val))?,
+                    );
+                }
+                Long("before") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    before = Some(
+                        CommentId::from_str(&val)
+                            .map_err(|_| anyhow!("invalid comment id '{}'", val))?,
+                    );
+                }
+                Long("after") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = val.to_string_lossy();
+                    after = Some(
+                        CommentId::from_str(&val)
+                            .map_err(|_| anyhow!("invalid comment id '{}'", val))?,
+                    );
+                }
+                Long("limit") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    limit = Some(
+                        val.to_string_lossy()
+                            .parse()
+                            .map_err(|_| anyhow!("invalid limit '{}'", val.to_string_lossy()))?,
+                    );
+                }
                 Long("assigned") | Short('a') if assigned.is_none() => {
                     if let Ok(val) = parser.value() {
                         let val = val.to_string_lossy();
@@ -162,6 +320,9 @@ impl Args for Options {
                     "d" | "delete" => op = Some(OperationName::Delete),
                     "l" | "list" => op = Some(OperationName::List),
                     "r" | "react" => op = Some(OperationName::React),
+                    "assign" => op = Some(OperationName::Assign),
+                    "unassign" => op = Some(OperationName::Unassign),
+                    "comment" => op = Some(OperationName::Comment),
 
                     unknown => anyhow::bail!("unknown operation '{}'", unknown),
                 },
@@ -186,6 +347,16 @@ impl Args for Options {
             OperationName::Show => Operation::Show {
                 id: id.ok_or_else(|| anyhow!("an issue id must be provided"))?,
                 json: json_out,
+                page: Page {
+                    before,
+                    after,
+                    limit,
+                },
+            },
+            OperationName::Comment => Operation::Comment {
+                id: id.ok_or_else(|| anyhow!("an issue id must be provided"))?,
+                reply_to,
+                message,
             },
             OperationName::State => Operation::State {
                 id: id.ok_or_else(|| anyhow!("an issue id must be provided"))?,
@@ -198,7 +369,21 @@ impl Args for Options {
             OperationName::Delete => Operation::Delete {
                 id: id.ok_or_else(|| anyhow!("an issue id to remove must be provided"))?,
             },
-            OperationName::List => Operation::List { assigned },
+            OperationName::Assign => Operation::Assign {
+                id: id.ok_or_else(|| anyhow!("an issue id must be provided"))?,
+                assignees,
+            },
+            OperationName::Unassign => Operation::Unassign {
+                id: id.ok_or_else(|| anyhow!("an issue id must be provided"))?,
+                assignees,
+            },
+            OperationName::List => Operation::List {
+                assigned,
+                state: list_state,
+                author,
+                tags,
+                search,
+            },
         };
 
         Ok((Options { op }, vec![]))
@@ -220,7 +405,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
         } => {
             issues.create(title, description, &[], &signer)?;
         }
-        Operation::Show { id, json } => {
+        Operation::Show { id, json, page } => {
             let error_message = "No issue with the given ID exists";
             let mut _output: String = String::from(error_message);
 
@@ -230,7 +415,21 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
             }
 
             let issue = issues.get(&id)?.context(_output)?;
-            show_issue(&issue, id, json)?;
+            show_issue(&issue, id, json, &page)?;
+        }
+        Operation::Comment {
+            id,
+            reply_to,
+            message,
+        } => {
+            let mut issue = issues.get_mut(&id)?;
+            let body = match message {
+                Some(message) => message,
+                None => term::Editor::new()
+                    .edit("")?
+                    .ok_or_else(|| anyhow!("a comment message must be provided"))?,
+            };
+            issue.comment(body.trim(), reply_to, &signer)?;
         }
         Operation::State { id, state } => {
             let mut issue = issues.get_mut(&id)?;
@@ -242,6 +441,32 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 issue.react(comment_id, reaction, &signer)?;
             }
         }
+        Operation::Assign { id, assignees } => {
+            if assignees.is_empty() {
+                anyhow::bail!("at least one `--add <key>` is required");
+            }
+            let mut issue = issues.get_mut(&id)?;
+            // The COB `assign` action replaces the whole assignee set, so `--add`
+            // is a read-modify-write: union the requested keys onto the current set.
+            let mut set: BTreeSet<PublicKey> = issue.assigned().cloned().collect();
+            set.extend(assignees);
+            issue.assign(set, &signer)?;
+        }
+        Operation::Unassign { id, assignees } => {
+            if assignees.is_empty() {
+                anyhow::bail!("at least one `--delete <key>` is required");
+            }
+            let mut issue = issues.get_mut(&id)?;
+            // Likewise, `--delete` subtracts the requested keys from the current
+            // set and writes the remainder back through the replacing action.
+            let remove: BTreeSet<PublicKey> = assignees.into_iter().collect();
+            let set: BTreeSet<PublicKey> = issue
+                .assigned()
+                .cloned()
+                .filter(|key| !remove.contains(key))
+                .collect();
+            issue.assign(set, &signer)?;
+        }
         Operation::Create { title, description } => {
             let meta = Metadata {
                 title: title.unwrap_or("Enter a title".to_owned()),
@@ -286,7 +511,13 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 )?;
             }
         }
-        Operation::List { assigned } => {
+        Operation::List {
+            assigned,
+            state,
+            author,
+            tags,
+            search,
+        } => {
             let assignee = match assigned {
                 Some(Assigned::Me) => Some(*profile.id()),
                 Some(Assigned::Peer(id)) => Some(id),
@@ -301,6 +532,27 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 if Some(true) == assignee.map(|a| !assigned.contains(&&a)) {
                     continue;
                 }
+                if let Some(state) = &state {
+                    if !state.matches(issue.state()) {
+                        continue;
+                    }
+                }
+                if let Some(author) = &author {
+                    if issue.author().id() != author {
+                        continue;
+                    }
+                }
+                if !tags.iter().all(|t| issue.tags().any(|it| it == t)) {
+                    continue;
+                }
+                if let Some(search) = &search {
+                    let title = issue.title().to_lowercase();
+                    let description = issue.description().unwrap_or("").to_lowercase();
+                    let needle = search.to_lowercase();
+                    if !title.contains(&needle) && !description.contains(&needle) {
+                        continue;
+                    }
+                }
 
                 let assigned: String = assigned
                     .iter()
@@ -310,6 +562,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
                 t.push([
                     id.to_string(),
                     format!("{:?}", issue.title()),
+                    issue.state().to_string(),
                     assigned.to_string(),
                 ]);
             }
@@ -361,18 +614,88 @@ impl<'a> FromIterator<(&'a CommentId, &'a thread::Comment)> for Comments {
     }
 }
 
+/// Return the window of comments selected by `page`, in timestamp order,
+/// along with the ids of the first and last comments in the window. The
+/// boundary ids let callers page backwards (`--before <first>`) or forwards
+/// (`--after <last>`).
+fn paginate<'a>(
+    comments: impl Iterator<Item = (&'a CommentId, &'a thread::Comment)>,
+    page: &Page,
+) -> (
+    Vec<(&'a CommentId, &'a thread::Comment)>,
+    Option<CommentId>,
+    Option<CommentId>,
+) {
+    let mut all: Vec<_> = comments.collect();
+    all.sort_by_key(|(id, c)| (c.timestamp(), **id));
+
+    let ids: Vec<CommentId> = all.iter().map(|(id, _)| **id).collect();
+    let (start, end) = window_bounds(&ids, page.after.as_ref(), page.before.as_ref(), page.limit);
+    let window = all[start..end].to_vec();
+
+    let first = window.first().map(|(id, _)| **id);
+    let last = window.last().map(|(id, _)| **id);
+
+    (window, first, last)
+}
+
+/// Compute the `[start, end)` slice of `ids` (already in display order)
+/// selected by the page cursors. `after`/`before` are exclusive boundaries;
+/// a cursor that is not present is ignored. `limit` caps the window, keeping
+/// its tail when paging backwards (`before` without `after`) so the `first`
+/// cursor walks back through the thread, and its head otherwise.
+fn window_bounds<T: PartialEq>(
+    ids: &[T],
+    after: Option<&T>,
+    before: Option<&T>,
+    limit: Option<usize>,
+) -> (usize, usize) {
+    let mut start = 0;
+    let mut end = ids.len();
+
+    if let Some(after) = after {
+        if let Some(pos) = ids.iter().position(|id| id == after) {
+            start = pos + 1;
+        }
+    }
+    if let Some(before) = before {
+        if let Some(pos) = ids.iter().position(|id| id == before) {
+            end = pos;
+        }
+    }
+    end = end.max(start);
+
+    if let Some(limit) = limit {
+        if before.is_some() && after.is_none() {
+            start = end.saturating_sub(limit).max(start);
+        } else {
+            end = (start + limit).min(end);
+        }
+    }
+    (start, end)
+}
+
 fn show_issue(
     issue: &issue::Issue,
     issue_id: IssueId,
     json_output: Option<bool>,
+    page: &Page,
 ) -> anyhow::Result<()> {
+    let (window, first, last) = paginate(issue.comments(), page);
+
     if json_output == Some(true) {
+        let discussion: Comments = window.iter().map(|(id, c)| (*id, *c)).collect();
+
         term::print(json!({
             "id": issue_id.to_string(),
             "author": issue.author(),
             "title": issue.title(),
             "description": issue.description(),
-            "discussion": issue.comments().collect::<Comments>(),
+            "discussion": discussion,
+            "page": {
+                "first": first.map(|id| id.to_string()),
+                "last": last.map(|id| id.to_string()),
+            },
             "tags": issue.tags().collect::<Vec<_>>(),
             "state": issue.state()
         }))
@@ -387,6 +710,65 @@ fn show_issue(
         term::info!("assignees: {}", assignees.join(", "));
 
         term::info!("{}", issue.description().unwrap_or(""));
+
+        for (id, comment) in window {
+            // The root comment carries the issue description, already printed
+            // above, so skip it to avoid duplicating the body.
+            let Some(reply_to) = comment.reply_to() else {
+                continue;
+            };
+            term::info!("comment {} (reply to {}):", id, reply_to);
+            term::info!("{}", comment.body());
+        }
+        if let (Some(first), Some(last)) = (first, last) {
+            term::info!("page: {}..{}", first, last);
+        }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::window_bounds;
+
+    #[test]
+    fn test_window_all() {
+        let ids = [1, 2, 3, 4, 5];
+        assert_eq!(window_bounds(&ids, None, None, None), (0, 5));
+    }
+
+    #[test]
+    fn test_window_after() {
+        let ids = [1, 2, 3, 4, 5];
+        // `--after 2` drops everything up to and including the cursor.
+        assert_eq!(window_bounds(&ids, Some(&2), None, None), (2, 5));
+    }
+
+    #[test]
+    fn test_window_after_limit() {
+        let ids = [1, 2, 3, 4, 5];
+        // Forward paging keeps the head of the remainder.
+        assert_eq!(window_bounds(&ids, Some(&1), None, Some(2)), (1, 3));
+    }
+
+    #[test]
+    fn test_window_before_limit_keeps_tail() {
+        let ids = [1, 2, 3, 4, 5];
+        // `--before 5 --limit 2` must keep the two comments *preceding* the
+        // cursor, not the oldest two.
+        assert_eq!(window_bounds(&ids, None, Some(&5), Some(2)), (2, 4));
+    }
+
+    #[test]
+    fn test_window_combined_bounds() {
+        let ids = [1, 2, 3, 4, 5];
+        assert_eq!(window_bounds(&ids, Some(&1), Some(&5), None), (1, 4));
+    }
+
+    #[test]
+    fn test_window_unknown_cursor() {
+        let ids = [1, 2, 3, 4, 5];
+        // A cursor that is not present is ignored, leaving the bound open.
+        assert_eq!(window_bounds(&ids, Some(&9), Some(&9), None), (0, 5));
+    }
+}
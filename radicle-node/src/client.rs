@@ -11,7 +11,14 @@ use crate::transport::Transport;
 use crate::wire::Wire;
 use crate::{address, service};
 
+pub mod admin;
+pub mod event;
 pub mod handle;
+pub mod metrics;
+
+pub use event::WireEvent;
+pub use handle::{Filter, Subscribers, Subscription};
+pub use metrics::Metrics;
 
 /// Directory in `$RAD_HOME` under which node-specific files are stored.
 pub const NODE_DIR: &str = "node";
@@ -44,6 +51,8 @@ pub struct Config {
     pub service: service::Config,
     /// Client listen addresses.
     pub listen: Vec<net::SocketAddr>,
+    /// Optional address for the read-only admin and metrics listener.
+    pub admin: Option<net::SocketAddr>,
 }
 
 impl Config {
@@ -64,6 +73,7 @@ impl Default for Config {
         Self {
             service: service::Config::default(),
             listen: vec![([0, 0, 0, 0], 0).into()],
+            admin: None,
         }
     }
 }
@@ -76,7 +86,9 @@ pub struct Client<R: Reactor> {
     commands: chan::Receiver<service::Command>,
     shutdown: chan::Sender<()>,
     listening: chan::Receiver<net::SocketAddr>,
+    subscribers: Subscribers,
     events: Events,
+    metrics: Metrics,
 }
 
 impl<R: Reactor> Client<R> {
@@ -85,7 +97,12 @@ impl<R: Reactor> Client<R> {
         let (shutdown, shutdown_recv) = chan::bounded(1);
         let (listening_send, listening) = chan::bounded(1);
         let reactor = R::new(shutdown_recv, listening_send)?;
-        let events = Events {};
+        let subscribers = Subscribers::default();
+        let metrics = Metrics::default();
+        let events = Events {
+            subscribers: subscribers.clone(),
+            metrics: metrics.clone(),
+        };
 
         Ok(Self {
             profile,
@@ -94,7 +111,9 @@ impl<R: Reactor> Client<R> {
             commands,
             listening,
             shutdown,
+            subscribers,
             events,
+            metrics,
         })
     }
 
@@ -110,6 +129,14 @@ impl<R: Reactor> Client<R> {
 
         log::info!("Initializing client ({:?})..", network);
 
+        let metrics = self.metrics.clone();
+        // Boot-time snapshots of the routing table and address book, taken
+        // before they are handed off to the service. These are one-shot
+        // samples, not live gauges; only the connected-peers count is kept
+        // current, off the event stream in `Events::publish`.
+        metrics.set_routing_size(routing.len()?);
+        metrics.set_known_addresses(addresses.len()?);
+
         let service = service::Service::new(
             config.service,
             RefClock::from(time),
@@ -120,6 +147,11 @@ impl<R: Reactor> Client<R> {
             rng,
         );
 
+        if let Some(addr) = config.admin {
+            log::info!("Starting admin listener on {}..", addr);
+            admin::spawn(addr, metrics.clone(), config.listen.clone())?;
+        }
+
         self.reactor.run(
             &config.listen,
             Transport::new(Wire::new(service)),
@@ -137,14 +169,28 @@ impl<R: Reactor> Client<R> {
             commands: self.handle.clone(),
             shutdown: self.shutdown.clone(),
             listening: self.listening.clone(),
+            subscribers: self.subscribers.clone(),
         }
     }
 }
 
-pub struct Events {}
+/// Publishes service events to the node's subscribers.
+pub struct Events {
+    subscribers: Subscribers,
+    metrics: Metrics,
+}
 
 impl nakamoto_net::Publisher<service::Event> for Events {
     fn publish(&mut self, e: service::Event) {
-        log::info!("Received event {:?}", e);
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("Received event {}", WireEvent::from(&e).to_json());
+        }
+        // Keep the connected-peers gauge in step with the live event stream.
+        match &e {
+            service::Event::PeerConnected { nid } => self.metrics.peer_connected(*nid),
+            service::Event::PeerDisconnected { nid } => self.metrics.peer_disconnected(nid),
+            _ => {}
+        }
+        self.subscribers.publish(&e);
     }
 }
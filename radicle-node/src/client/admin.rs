@@ -0,0 +1,77 @@
+//! Read-only admin HTTP listener.
+//!
+//! Exposes the node's [`Metrics`] over `GET /metrics` in the Prometheus text
+//! exposition format, and a small JSON view of the node over `GET /` for
+//! operators to inspect a running node without a custom RPC client. The
+//! listener is optional and only started when [`Config::admin`] is set.
+//!
+//! [`Config::admin`]: crate::client::Config::admin
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+use super::metrics::Metrics;
+
+/// Spawn the admin listener on a background thread.
+pub fn spawn(addr: SocketAddr, metrics: Metrics, listen: Vec<SocketAddr>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+
+    thread::Builder::new()
+        .name("admin".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        if let Err(e) = respond(stream, &metrics, &listen) {
+                            log::warn!("Admin request failed: {e}");
+                        }
+                    }
+                    Err(e) => log::warn!("Admin connection failed: {e}"),
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+/// Parse the request line and dispatch to the matching route.
+fn respond(mut stream: TcpStream, metrics: &Metrics, listen: &[SocketAddr]) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    match path {
+        "/metrics" => {
+            write_response(&mut stream, "200 OK", "text/plain; version=0.0.4", &metrics.encode())
+        }
+        "/" => write_response(&mut stream, "200 OK", "application/json", &json(metrics, listen)),
+        _ => write_response(&mut stream, "404 Not Found", "application/json", "{}"),
+    }
+}
+
+/// Render the JSON node overview: connected peers and listen addresses.
+fn json(metrics: &Metrics, listen: &[SocketAddr]) -> String {
+    let peers = metrics
+        .peers()
+        .iter()
+        .map(|p| format!("\"{p}\""))
+        .collect::<Vec<_>>()
+        .join(",");
+    let addrs = listen
+        .iter()
+        .map(|a| format!("\"{a}\""))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{{\"peers\":[{peers}],\"listen\":[{addrs}]}}")
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )
+}
@@ -0,0 +1,139 @@
+//! Stable JSON wire form for [`service::Event`].
+//!
+//! External, non-Rust consumers of the event stream should not depend on the
+//! `Debug` rendering of internal types. [`WireEvent`] is the serialization
+//! contract: every event is encoded as
+//!
+//! ```json
+//! { "event": "<name>", "payload": { ... } }
+//! ```
+//!
+//! The `event` names listed on [`Event`] are **stable** and must not be
+//! renamed. Events a node does not recognize — for instance ones emitted by a
+//! newer peer — are preserved through the [`WireEvent::Dynamic`] variant
+//! rather than dropped, so the format is forward-compatible.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::identity::Id;
+use crate::service;
+use crate::service::NodeId;
+
+/// The JSON wire form of a [`service::Event`].
+///
+/// Known variants deserialize into their typed form; anything else round-trips
+/// through [`WireEvent::Dynamic`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WireEvent {
+    /// An event recognized by this node.
+    Known(Event),
+    /// An unrecognized event, kept verbatim for forward compatibility.
+    Dynamic(Envelope),
+}
+
+/// The typed, recognized events. Each variant encodes as
+/// `{ "event": "<name>", "payload": { ... } }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "payload")]
+pub enum Event {
+    /// A peer connection was established.
+    PeerConnected { nid: NodeId },
+    /// A peer connection was dropped.
+    PeerDisconnected { nid: NodeId },
+    /// Refs were fetched for a repository.
+    RefsFetched { remote: NodeId, rid: Id },
+}
+
+/// A generic, untyped event envelope used for unknown events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// The event name.
+    pub event: String,
+    /// The opaque payload.
+    pub payload: Value,
+}
+
+impl WireEvent {
+    /// Encode the event as its JSON wire form.
+    pub fn to_json(&self) -> String {
+        // The derived serialization is infallible for these types.
+        serde_json::to_string(self).expect("WireEvent is always serializable")
+    }
+}
+
+impl From<&service::Event> for WireEvent {
+    fn from(e: &service::Event) -> Self {
+        match e {
+            service::Event::PeerConnected { nid } => {
+                WireEvent::Known(Event::PeerConnected { nid: *nid })
+            }
+            service::Event::PeerDisconnected { nid } => {
+                WireEvent::Known(Event::PeerDisconnected { nid: *nid })
+            }
+            service::Event::RefsFetched { remote, rid } => {
+                WireEvent::Known(Event::RefsFetched {
+                    remote: *remote,
+                    rid: *rid,
+                })
+            }
+            // Events without a typed wire representation are relayed through
+            // the dynamic envelope, so consumers still observe them. We preserve
+            // the event's real name and structure via serde rather than a lossy
+            // `Debug` dump, keeping the form parseable and forward-compatible.
+            other => {
+                let value = serde_json::to_value(other).unwrap_or(Value::Null);
+                // `service::Event` is an externally-tagged enum, so it encodes
+                // as `{ "<Variant>": <payload> }`; lift the name out of the tag.
+                let (event, payload) = match value {
+                    Value::Object(map) if map.len() == 1 => {
+                        map.into_iter().next().expect("map has a single entry")
+                    }
+                    value => ("unknown".to_owned(), value),
+                };
+                WireEvent::Dynamic(Envelope { event, payload })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // A well-formed node id; its exact value is irrelevant to round-tripping.
+    fn node_id() -> NodeId {
+        NodeId::from_str("z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi").unwrap()
+    }
+
+    fn roundtrip(event: &WireEvent) {
+        let decoded: WireEvent = serde_json::from_str(&event.to_json()).unwrap();
+        assert_eq!(&decoded, event);
+    }
+
+    #[test]
+    fn test_known_roundtrip() {
+        let nid = node_id();
+        roundtrip(&WireEvent::Known(Event::PeerConnected { nid }));
+        roundtrip(&WireEvent::Known(Event::PeerDisconnected { nid }));
+    }
+
+    #[test]
+    fn test_dynamic_roundtrip() {
+        // An event this node doesn't recognize must survive verbatim.
+        let event = WireEvent::Dynamic(Envelope {
+            event: "SomeFutureEvent".to_owned(),
+            payload: serde_json::json!({ "count": 7, "label": "future" }),
+        });
+        roundtrip(&event);
+    }
+
+    #[test]
+    fn test_unknown_decodes_as_dynamic() {
+        let json = r#"{"event":"SomeFutureEvent","payload":{"count":7}}"#;
+        let decoded: WireEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(decoded, WireEvent::Dynamic(_)));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::net;
+use std::sync::{Arc, Mutex};
+
+use crossbeam_channel as chan;
+use nakamoto_net::Waker;
+
+use crate::identity::Id;
+use crate::service;
+
+/// A topic an event can be classified under, used for per-subscriber
+/// filtering. Mirrors the interesting variants of [`service::Event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// A peer connection was established.
+    PeerConnected,
+    /// A peer connection was dropped.
+    PeerDisconnected,
+    /// Refs were fetched for a repository.
+    RefsFetched,
+    /// Any other event.
+    Other,
+}
+
+impl Topic {
+    /// Classify an event under a topic.
+    fn of(e: &service::Event) -> Self {
+        match e {
+            service::Event::PeerConnected { .. } => Topic::PeerConnected,
+            service::Event::PeerDisconnected { .. } => Topic::PeerDisconnected,
+            service::Event::RefsFetched { .. } => Topic::RefsFetched,
+            _ => Topic::Other,
+        }
+    }
+}
+
+/// A filter deciding which events a subscriber receives. Empty filters match
+/// everything; otherwise an event must match *every* active predicate, in the
+/// style of a dataspace topic filter.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// If set, only events classified under one of these topics are delivered.
+    topics: Option<HashSet<Topic>>,
+    /// If set, ref-update events are restricted to this repository.
+    rid: Option<Id>,
+}
+
+impl Filter {
+    /// A filter that matches every event.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Restrict delivery to the given topics.
+    pub fn topics(mut self, topics: impl IntoIterator<Item = Topic>) -> Self {
+        self.topics
+            .get_or_insert_with(HashSet::new)
+            .extend(topics);
+        self
+    }
+
+    /// Restrict ref-update events to a single repository.
+    pub fn repo(mut self, rid: Id) -> Self {
+        self.rid = Some(rid);
+        self
+    }
+
+    /// Whether this filter matches the given event.
+    pub fn matches(&self, e: &service::Event) -> bool {
+        if let Some(topics) = &self.topics {
+            if !topics.contains(&Topic::of(e)) {
+                return false;
+            }
+        }
+        if let Some(rid) = self.rid {
+            if let service::Event::RefsFetched { rid: event_rid, .. } = e {
+                if *event_rid != rid {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// A single registered subscriber: a filter and the sending half of its
+/// channel.
+struct Subscriber {
+    id: u64,
+    filter: Filter,
+    channel: chan::Sender<service::Event>,
+}
+
+/// Shared, cloneable registry of event subscribers. Held by both the
+/// [`super::Events`] publisher and every [`Handle`], so subscriptions created
+/// through a handle are visible to the running reactor.
+#[derive(Clone, Default)]
+pub struct Subscribers(Arc<Mutex<Inner>>);
+
+#[derive(Default)]
+struct Inner {
+    next: u64,
+    subscribers: Vec<Subscriber>,
+}
+
+impl Subscribers {
+    /// Register a new subscriber with the given filter, returning its id and
+    /// the receiving half of its channel.
+    fn register(&self, filter: Filter) -> (u64, chan::Receiver<service::Event>) {
+        let (tx, rx) = chan::unbounded();
+        let mut inner = self.0.lock().unwrap();
+        let id = inner.next;
+        inner.next += 1;
+        inner.subscribers.push(Subscriber {
+            id,
+            filter,
+            channel: tx,
+        });
+        (id, rx)
+    }
+
+    /// Remove the subscriber with the given id, if present.
+    fn remove(&self, id: u64) {
+        let mut inner = self.0.lock().unwrap();
+        inner.subscribers.retain(|s| s.id != id);
+    }
+
+    /// Fan an event out to every subscriber whose filter matches it, dropping
+    /// any matching subscriber whose receiver has been disconnected.
+    /// Subscribers the event does not match are kept as they are.
+    pub fn publish(&self, e: &service::Event) {
+        let mut inner = self.0.lock().unwrap();
+        inner
+            .subscribers
+            .retain(|s| !s.filter.matches(e) || s.channel.send(e.clone()).is_ok());
+    }
+}
+
+/// An active event subscription. Yields [`service::Event`]s over its channel
+/// and unregisters itself from the node when dropped.
+pub struct Subscription {
+    id: u64,
+    subscribers: Subscribers,
+    /// The receiving half of the subscription's channel.
+    pub events: chan::Receiver<service::Event>,
+}
+
+impl Subscription {
+    /// Explicitly unsubscribe, releasing the channel. Equivalent to dropping
+    /// the subscription.
+    pub fn unsubscribe(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.subscribers.remove(self.id);
+    }
+}
+
+/// A handle to a running [`super::Client`].
+pub struct Handle<W: Waker> {
+    pub(super) waker: W,
+    pub(super) commands: chan::Sender<service::Command>,
+    pub(super) shutdown: chan::Sender<()>,
+    pub(super) listening: chan::Receiver<net::SocketAddr>,
+    pub(super) subscribers: Subscribers,
+}
+
+impl<W: Waker> Handle<W> {
+    /// Subscribe to node events matching the given filter. Use [`Filter::all`]
+    /// to receive every event. The subscription unregisters itself when
+    /// dropped.
+    pub fn subscribe(&self, filter: Filter) -> Subscription {
+        let (id, events) = self.subscribers.register(filter);
+        Subscription {
+            id,
+            subscribers: self.subscribers.clone(),
+            events,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Prometheus-compatible metrics for the node.
+//!
+//! A [`Metrics`] handle is shared between the subsystems that produce counts
+//! — the routing table, address book — and the admin server that scrapes
+//! them. The connected-peers gauge tracks live state off the event stream;
+//! routing size and known addresses are sampled at startup.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::service::NodeId;
+
+/// A cloneable handle to the node's metrics registry.
+#[derive(Clone)]
+pub struct Metrics(Arc<Inner>);
+
+struct Inner {
+    started: Instant,
+    routing_size: AtomicUsize,
+    known_addresses: AtomicUsize,
+    peers: Mutex<BTreeSet<NodeId>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self(Arc::new(Inner {
+            started: Instant::now(),
+            routing_size: AtomicUsize::new(0),
+            known_addresses: AtomicUsize::new(0),
+            peers: Mutex::new(BTreeSet::new()),
+        }))
+    }
+}
+
+impl Metrics {
+    /// Record the current size of the routing table.
+    pub fn set_routing_size(&self, n: usize) {
+        self.0.routing_size.store(n, Ordering::Relaxed);
+    }
+
+    /// Record the number of known addresses.
+    pub fn set_known_addresses(&self, n: usize) {
+        self.0.known_addresses.store(n, Ordering::Relaxed);
+    }
+
+    /// Mark a peer as connected.
+    pub fn peer_connected(&self, nid: NodeId) {
+        self.0.peers.lock().unwrap().insert(nid);
+    }
+
+    /// Mark a peer as disconnected.
+    pub fn peer_disconnected(&self, nid: &NodeId) {
+        self.0.peers.lock().unwrap().remove(nid);
+    }
+
+    /// The set of currently connected peers.
+    pub fn peers(&self) -> BTreeSet<NodeId> {
+        self.0.peers.lock().unwrap().clone()
+    }
+
+    /// Seconds since the node started.
+    pub fn uptime(&self) -> u64 {
+        self.0.started.elapsed().as_secs()
+    }
+
+    /// Encode the registry in the Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        let connected = self.0.peers.lock().unwrap().len();
+        let mut out = String::new();
+
+        gauge(
+            &mut out,
+            "radicle_routing_entries",
+            "Number of entries in the routing table.",
+            self.0.routing_size.load(Ordering::Relaxed) as u64,
+        );
+        gauge(
+            &mut out,
+            "radicle_known_addresses",
+            "Number of known peer addresses.",
+            self.0.known_addresses.load(Ordering::Relaxed) as u64,
+        );
+        gauge(
+            &mut out,
+            "radicle_connected_peers",
+            "Number of currently connected peers.",
+            connected as u64,
+        );
+        gauge(
+            &mut out,
+            "radicle_uptime_seconds",
+            "Seconds since the node started.",
+            self.uptime(),
+        );
+        out
+    }
+}
+
+/// Write a single `gauge` metric with its `# HELP`/`# TYPE` preamble.
+fn gauge(out: &mut String, name: &str, help: &str, value: u64) {
+    sample(out, name, "gauge", help, value);
+}
+
+fn sample(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    writeln!(out, "# HELP {name} {help}").ok();
+    writeln!(out, "# TYPE {name} {kind}").ok();
+    writeln!(out, "{name} {value}").ok();
+}